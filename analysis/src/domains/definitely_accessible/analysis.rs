@@ -100,6 +100,13 @@ impl<'mir, 'tcx: 'mir> DefinitelyAccessibleAnalysis<'mir, 'tcx> {
         borrowed: &MaybeBorrowedState<'tcx>,
     ) -> DefinitelyAccessibleState<'tcx> {
         let mut definitely_accessible: FxHashSet<_> = def_init.get_def_init_places().clone();
+        // Two-phase mutable borrows are only *reserved* at the borrow site and do not take the
+        // place away until they are *activated* at their first use (the machinery behind the
+        // `MUTABLE_BORROW_RESERVATION_CONFLICT` lint). Between reservation and activation the
+        // original place is still freely readable. `MaybeBorrowedState` keeps those reserved
+        // borrows in a separate set and only moves a place into `maybe_mut_borrowed` once the
+        // borrow is activated, so iterating the active set here subtracts the place at exactly
+        // the activation point and not before.
         for &place in borrowed.get_maybe_mut_borrowed().iter() {
             self.remove_place_from_set(place, &mut definitely_accessible);
         }
@@ -117,15 +124,74 @@ impl<'mir, 'tcx: 'mir> DefinitelyAccessibleAnalysis<'mir, 'tcx> {
     fn remove_place_from_set(&self, to_remove: mir::Place<'tcx>, set: &mut FxHashSet<mir::Place<'tcx>>) {
         let old_set = mem::take(set);
         for old_place in old_set {
-            if is_prefix(to_remove, old_place) {
-                // Unpack `old_place` and remove `to_remove`.
-                set.extend(expand(&self.body_with_facts.body, self.tcx, old_place, to_remove));
-            } else if is_prefix(old_place, to_remove) {
-                // `to_remove` is a prefix of `old_place`. So, it should *not* be added to `set`.
-            } else {
-                // `old_place` and `to_remove` are unrelated.
+            if !self.places_conflict(to_remove, old_place) {
+                // The borrowed place cannot alias `old_place`, so it stays accessible.
                 set.insert(old_place);
+            } else if is_prefix(to_remove, old_place) {
+                // `old_place` is a prefix of `to_remove`: unpack `old_place` and remove only
+                // the conflicting sub-places. Expansion can reintroduce siblings of the borrowed
+                // field; for a union those siblings alias `to_remove` and must be dropped too, so
+                // we filter out any expanded place that still conflicts with the borrow.
+                set.extend(
+                    expand(&self.body_with_facts.body, self.tcx, old_place, to_remove)
+                        .into_iter()
+                        .filter(|&sub_place| !self.places_conflict(to_remove, sub_place)),
+                );
+            } else {
+                // The borrowed place is equal to or a prefix of `old_place`, or aliases it
+                // through a union/array projection: the whole owned place is lost.
+            }
+        }
+    }
+
+    /// Decide whether the borrowed place `a` and the owned place `b` may refer to overlapping
+    /// memory, modeled on rustc's borrowck `places_conflict`.
+    ///
+    /// The two projection chains are walked in lockstep over their common prefix. At the first
+    /// position where they differ we may only answer "disjoint" if disjointness is provable:
+    /// two `Field`s of the same struct/tuple/enum variant with different indices are disjoint,
+    /// but two `Field`s of a `union` share storage and always conflict, and two indexing
+    /// projections into the same array or slice conflict because we cannot prove the indices
+    /// distinct. If one chain is exhausted first the places are in a prefix relationship and
+    /// therefore conflict.
+    fn places_conflict(&self, a: mir::Place<'tcx>, b: mir::Place<'tcx>) -> bool {
+        if a.local != b.local {
+            return false;
+        }
+        let body = &self.body_with_facts.body;
+        let mut base_ty = mir::tcx::PlaceTy::from_ty(body.local_decls[a.local].ty);
+        for (a_elem, b_elem) in a.projection.iter().zip(b.projection.iter()) {
+            match (a_elem, b_elem) {
+                (mir::ProjectionElem::Field(a_f, _), mir::ProjectionElem::Field(b_f, _))
+                    if a_f != b_f =>
+                {
+                    // Distinct fields are disjoint, unless they alias through a union.
+                    return base_ty.ty.is_union();
+                }
+                (
+                    mir::ProjectionElem::Index(..)
+                    | mir::ProjectionElem::ConstantIndex { .. }
+                    | mir::ProjectionElem::Subslice { .. },
+                    mir::ProjectionElem::Index(..)
+                    | mir::ProjectionElem::ConstantIndex { .. }
+                    | mir::ProjectionElem::Subslice { .. },
+                ) => {
+                    // Indexing into the same array or slice: the indices may coincide.
+                }
+                (mir::ProjectionElem::Downcast(..), mir::ProjectionElem::Downcast(..)) => {
+                    // Enum variants share storage, so downcasts to different variants never
+                    // disambiguate places: keep walking.
+                }
+                (a_elem, b_elem) if a_elem != b_elem => {
+                    // Any other pair of differing projection elements: the places are treated
+                    // as disjoint.
+                    return false;
+                }
+                _ => {}
             }
+            base_ty = base_ty.projection_ty(self.tcx, b_elem);
         }
+        // One projection chain is a prefix of the other (or they are equal): they conflict.
+        true
     }
 }
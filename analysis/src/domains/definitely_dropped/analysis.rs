@@ -0,0 +1,106 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AnalysisResult, FixpointEngine},
+    domains::{
+        DefinitelyDroppedState, EverInitializedAnalysis, EverInitializedState,
+        MaybeInitializedAnalysis, MaybeInitializedState,
+    },
+    PointwiseState,
+};
+use rustc_middle::{mir, ty::TyCtxt};
+use rustc_span::def_id::DefId;
+use rustc_data_structures::stable_set::FxHashSet;
+
+pub struct DefinitelyDroppedAnalysis<'mir, 'tcx: 'mir> {
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    body: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> DefinitelyDroppedAnalysis<'mir, 'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, def_id: DefId, body: &'mir mir::Body<'tcx>) -> Self {
+        DefinitelyDroppedAnalysis { tcx, def_id, body }
+    }
+
+    pub fn run_analysis<'body>(
+        &'body self,
+    ) -> AnalysisResult<PointwiseState<'body, 'tcx, DefinitelyDroppedState<'tcx>>> {
+        let body = self.body;
+        let ever_init_analysis = EverInitializedAnalysis::new(self.tcx, self.def_id, body);
+        // "Definitely uninitialized" is the complement of "maybe initialized"; it cannot be
+        // obtained from a maybe-uninitialized analysis (whose complement is definitely-init), so
+        // we compute maybe-initialized here and take its complement below.
+        let maybe_init_analysis = MaybeInitializedAnalysis::new(self.tcx, self.def_id, body);
+        let ever_init = ever_init_analysis.run_fwd_analysis()?;
+        let maybe_init = maybe_init_analysis.run_fwd_analysis()?;
+        let mut analysis_state = PointwiseState::default(body);
+
+        for (block, block_data) in body.basic_blocks().iter_enumerated() {
+            // Initialize the state before each statement
+            for statement_index in 0..=block_data.statements.len() {
+                let location = mir::Location {
+                    block,
+                    statement_index,
+                };
+                let ever_init_before = ever_init
+                    .lookup_before(location)
+                    .unwrap_or_else(|| panic!("No 'ever_init' state before location {:?}", location));
+                let maybe_init_before = maybe_init
+                    .lookup_before(location)
+                    .unwrap_or_else(|| {
+                        panic!("No 'maybe_init' state before location {:?}", location)
+                    });
+                let state = self.compute_dropped_state(ever_init_before, maybe_init_before);
+                analysis_state.set_before(location, state);
+            }
+
+            // Initialize the state of successors of terminators
+            let ever_init_after_block = ever_init
+                .lookup_after_block(block)
+                .unwrap_or_else(|| panic!("No 'ever_init' state after block {:?}", block));
+            let maybe_init_after_block = maybe_init
+                .lookup_after_block(block)
+                .unwrap_or_else(|| panic!("No 'maybe_init' state after block {:?}", block));
+            let dropped_after_block = analysis_state.lookup_mut_after_block(block);
+            for &successor in block_data.terminator().successors() {
+                let ever_init_after = ever_init_after_block.get(&successor).unwrap_or_else(|| {
+                    panic!("No 'ever_init' state from {:?} to {:?}", block, successor)
+                });
+                let maybe_init_after = maybe_init_after_block.get(&successor).unwrap_or_else(|| {
+                    panic!("No 'maybe_init' state from {:?} to {:?}", block, successor)
+                });
+                let state = self.compute_dropped_state(ever_init_after, maybe_init_after);
+                dropped_after_block.insert(successor, state);
+            }
+        }
+
+        Ok(analysis_state)
+    }
+
+    /// Compute the `definitely_dropped` state as the places that have been ever initialized and
+    /// are now definitely uninitialized. A place is definitely dropped when it was owned on some
+    /// path and is uninitialized on *all* paths, i.e. it has certainly been moved out of or
+    /// dropped — as opposed to never having been owned.
+    fn compute_dropped_state(
+        &self,
+        ever_init: &EverInitializedState<'mir, 'tcx>,
+        maybe_init: &MaybeInitializedState<'mir, 'tcx>,
+    ) -> DefinitelyDroppedState<'tcx> {
+        // A place is definitely uninitialized iff it is *not* maybe initialized (uninit on every
+        // path). Intersecting the ever-initialized places with that set yields the places that
+        // were owned at some point and have since certainly been released.
+        let maybe_init_places = maybe_init.get_maybe_init_places();
+        let definitely_dropped: FxHashSet<_> = ever_init
+            .get_ever_init_places()
+            .iter()
+            .filter(|place| !maybe_init_places.contains(place))
+            .copied()
+            .collect();
+        DefinitelyDroppedState { definitely_dropped }
+    }
+}
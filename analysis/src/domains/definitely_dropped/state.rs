@@ -0,0 +1,36 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use rustc_data_structures::stable_set::FxHashSet;
+use rustc_middle::mir;
+use serde::{Serialize, Serializer};
+
+/// The set of places that are *definitely dropped*: places that have been initialized on some
+/// path (ever initialized) and are now definitely uninitialized, i.e. certainly moved out of or
+/// dropped on every path. This lets downstream passes distinguish "never owned" from "owned then
+/// dropped/moved".
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct DefinitelyDroppedState<'tcx> {
+    pub definitely_dropped: FxHashSet<mir::Place<'tcx>>,
+}
+
+impl<'tcx> DefinitelyDroppedState<'tcx> {
+    pub fn get_definitely_dropped(&self) -> &FxHashSet<mir::Place<'tcx>> {
+        &self.definitely_dropped
+    }
+}
+
+impl<'tcx> Serialize for DefinitelyDroppedState<'tcx> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut places: Vec<_> = self
+            .definitely_dropped
+            .iter()
+            .map(|place| format!("{:?}", place))
+            .collect();
+        places.sort();
+        places.serialize(serializer)
+    }
+}
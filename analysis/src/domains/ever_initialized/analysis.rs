@@ -0,0 +1,75 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AnalysisResult, FixpointEngine},
+    domains::EverInitializedState,
+};
+use rustc_middle::{mir, ty::TyCtxt};
+use rustc_span::def_id::DefId;
+
+/// A fixpoint analysis computing the places that have been *ever initialized* at each program
+/// point: a place is ever initialized if it has been initialized on at least one path reaching
+/// the location. Unlike definite initialization this set only grows along the way, so moving
+/// out of a place does not remove it. Mirrors rustc's `EverInitializedPlaces` dataflow.
+pub struct EverInitializedAnalysis<'mir, 'tcx: 'mir> {
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    body: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> EverInitializedAnalysis<'mir, 'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, def_id: DefId, body: &'mir mir::Body<'tcx>) -> Self {
+        EverInitializedAnalysis { tcx, def_id, body }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> FixpointEngine<'mir, 'tcx> for EverInitializedAnalysis<'mir, 'tcx> {
+    type State = EverInitializedState<'mir, 'tcx>;
+
+    fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
+    fn body(&self) -> &'mir mir::Body<'tcx> {
+        self.body
+    }
+
+    /// At the bottom of the lattice no place has been initialized yet.
+    fn new_bottom(&self) -> Self::State {
+        EverInitializedState::new_bottom(self.tcx, self.body)
+    }
+
+    /// On entry the arguments have been initialized by the caller.
+    fn new_initial(&self) -> Self::State {
+        EverInitializedState::new_initial(self.tcx, self.body)
+    }
+
+    fn need_to_widen(_counter: u32) -> bool {
+        // The domain is finite (the set of tracked places), so no widening is needed.
+        false
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::State,
+        location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) -> AnalysisResult<()> {
+        // Assigning to a place records it as ever initialized; moves and
+        // drops have no effect, since the fact is monotone.
+        state.apply_statement_effect(location, statement)
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &Self::State,
+        location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+    ) -> AnalysisResult<Vec<(mir::BasicBlock, Self::State)>> {
+        state.apply_terminator_effect(location, terminator)
+    }
+}
@@ -0,0 +1,112 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::abstract_interpretation::{AbstractState, AnalysisResult};
+use rustc_data_structures::stable_set::FxHashSet;
+use rustc_middle::{mir, ty::TyCtxt};
+
+/// The set of places that have been *ever initialized*: a place is in this set if it has been
+/// initialized on at least one path reaching the program point. This is a monotone forward
+/// may-analysis — moving out of or dropping a place does not remove it — mirroring rustc's
+/// `EverInitializedPlaces`.
+#[derive(Clone)]
+pub struct EverInitializedState<'mir, 'tcx: 'mir> {
+    ever_init: FxHashSet<mir::Place<'tcx>>,
+    tcx: TyCtxt<'tcx>,
+    body: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> PartialEq for EverInitializedState<'mir, 'tcx> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ever_init == other.ever_init
+    }
+}
+impl<'mir, 'tcx: 'mir> Eq for EverInitializedState<'mir, 'tcx> {}
+
+impl<'mir, 'tcx: 'mir> EverInitializedState<'mir, 'tcx> {
+    /// No place has been initialized yet.
+    pub fn new_bottom(tcx: TyCtxt<'tcx>, body: &'mir mir::Body<'tcx>) -> Self {
+        EverInitializedState {
+            ever_init: FxHashSet::default(),
+            tcx,
+            body,
+        }
+    }
+
+    /// On entry the arguments have been initialized by the caller.
+    pub fn new_initial(tcx: TyCtxt<'tcx>, body: &'mir mir::Body<'tcx>) -> Self {
+        let mut ever_init = FxHashSet::default();
+        for arg in 1..=body.arg_count {
+            ever_init.insert(mir::Place::from(mir::Local::from_usize(arg)));
+        }
+        EverInitializedState {
+            ever_init,
+            tcx,
+            body,
+        }
+    }
+
+    /// The places that have been ever initialized at this point.
+    pub fn get_ever_init_places(&self) -> &FxHashSet<mir::Place<'tcx>> {
+        &self.ever_init
+    }
+
+    fn gen(&mut self, place: mir::Place<'tcx>) {
+        self.ever_init.insert(place);
+    }
+
+    pub fn apply_statement_effect(
+        &mut self,
+        _location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) -> AnalysisResult<()> {
+        // Assigning to a place records it as ever initialized; the fact is monotone, so moves
+        // and drops have no effect.
+        if let mir::StatementKind::Assign(box (place, _)) = &statement.kind {
+            self.gen(*place);
+        }
+        Ok(())
+    }
+
+    pub fn apply_terminator_effect(
+        &self,
+        _location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+    ) -> AnalysisResult<Vec<(mir::BasicBlock, Self)>> {
+        let mut res = Vec::new();
+        for successor in terminator.successors() {
+            let mut state = self.clone();
+            match &terminator.kind {
+                mir::TerminatorKind::Call {
+                    destination: Some((place, target)),
+                    ..
+                } => {
+                    if *target == *successor {
+                        state.gen(*place);
+                    }
+                }
+                // The replacing value initializes `place`.
+                mir::TerminatorKind::DropAndReplace { place, .. } => state.gen(*place),
+                _ => {}
+            }
+            res.push((*successor, state));
+        }
+        Ok(res)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> AbstractState for EverInitializedState<'mir, 'tcx> {
+    fn is_bottom(&self) -> bool {
+        self.ever_init.is_empty()
+    }
+
+    fn join(&mut self, other: &Self) {
+        // May-analysis: a place is ever initialized if it is so on any incoming path.
+        self.ever_init.extend(other.ever_init.iter().copied());
+    }
+
+    fn widen(&mut self, _previous: &Self) {}
+}
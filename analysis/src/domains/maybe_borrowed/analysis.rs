@@ -0,0 +1,134 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::AnalysisResult, domains::MaybeBorrowedState, PointwiseState,
+};
+use rustc_borrowck::BodyWithBorrowckFacts;
+use rustc_middle::{mir, ty::TyCtxt};
+
+pub struct MaybeBorrowedAnalysis<'mir, 'tcx: 'mir> {
+    tcx: TyCtxt<'tcx>,
+    body_with_facts: &'mir BodyWithBorrowckFacts<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> MaybeBorrowedAnalysis<'mir, 'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, body_with_facts: &'mir BodyWithBorrowckFacts<'tcx>) -> Self {
+        MaybeBorrowedAnalysis {
+            tcx,
+            body_with_facts,
+        }
+    }
+
+    pub fn run_analysis<'body>(
+        &'body self,
+    ) -> AnalysisResult<PointwiseState<'body, 'tcx, MaybeBorrowedState<'tcx>>> {
+        let body = &self.body_with_facts.body;
+        let dominators = body.dominators();
+        let mut analysis_state = PointwiseState::default(body);
+
+        for (block, block_data) in body.basic_blocks().iter_enumerated() {
+            // Set the state before each statement.
+            for statement_index in 0..=block_data.statements.len() {
+                let location = mir::Location {
+                    block,
+                    statement_index,
+                };
+                let state = self.compute_borrowed_state(location, &dominators);
+                analysis_state.set_before(location, state);
+            }
+
+            // Set the state on each edge out of the terminator. The borrows live at the
+            // terminator are the ones flowing to the successors.
+            let terminator_location = mir::Location {
+                block,
+                statement_index: block_data.statements.len(),
+            };
+            let borrowed_after_block = analysis_state.lookup_mut_after_block(block);
+            for &successor in block_data.terminator().successors() {
+                let state = self.compute_borrowed_state(terminator_location, &dominators);
+                borrowed_after_block.insert(successor, state);
+            }
+        }
+
+        Ok(analysis_state)
+    }
+
+    /// Compute the set of maybe-borrowed places live at `location`, classifying two-phase
+    /// mutable borrows as reserved or fully active.
+    fn compute_borrowed_state(
+        &self,
+        location: mir::Location,
+        dominators: &rustc_middle::mir::Dominators<mir::BasicBlock>,
+    ) -> MaybeBorrowedState<'tcx> {
+        let borrow_set = &self.body_with_facts.borrow_set;
+        let mut state = MaybeBorrowedState::default();
+        for (_, borrow_data) in borrow_set.location_map.iter() {
+            if !self.is_loan_live_at(borrow_data, location) {
+                continue;
+            }
+            let borrowed_place = borrow_data.borrowed_place;
+            match borrow_data.kind {
+                mir::BorrowKind::Shared | mir::BorrowKind::Shallow => {
+                    state.insert_shared(borrowed_place);
+                }
+                mir::BorrowKind::Unique | mir::BorrowKind::Mut { .. } => {
+                    // Distinguish two-phase reservations from fully active borrows: the borrow
+                    // is only active from its activation location onwards; before that it is
+                    // merely reserved and the original place stays readable.
+                    match self.activation_location(borrow_data) {
+                        Some(activation) if location_precedes(dominators, location, activation) => {
+                            state.reserve_mut(borrowed_place);
+                        }
+                        Some(_) => state.activate_mut(borrowed_place),
+                        None => state.insert_mut(borrowed_place),
+                    }
+                }
+            }
+        }
+        state
+    }
+
+    /// The MIR location at which a two-phase borrow is first used and therefore activated, or
+    /// `None` for an ordinary (immediately active) borrow.
+    fn activation_location(
+        &self,
+        borrow_data: &rustc_borrowck::BorrowData<'tcx>,
+    ) -> Option<mir::Location> {
+        use rustc_borrowck::TwoPhaseActivation;
+        match borrow_data.activation_location {
+            TwoPhaseActivation::ActivatedAt(location) => Some(location),
+            TwoPhaseActivation::NotActivated | TwoPhaseActivation::NotTwoPhase => None,
+        }
+    }
+
+    /// Whether the loan created by `borrow_data` is live at `location` according to the
+    /// borrowck (polonius) facts.
+    fn is_loan_live_at(
+        &self,
+        borrow_data: &rustc_borrowck::BorrowData<'tcx>,
+        location: mir::Location,
+    ) -> bool {
+        // The concrete liveness query against the polonius output is delegated to the shared
+        // helper used by the other borrow-based analyses.
+        crate::mir_utils::is_loan_live_at(self.body_with_facts, borrow_data, location)
+    }
+}
+
+/// Whether `earlier` comes strictly before `later` in the pre-computed dominator ordering. A
+/// two-phase borrow reserved in one block and activated in a successor is treated as reserved at
+/// every location up to the activation statement.
+fn location_precedes(
+    dominators: &rustc_middle::mir::Dominators<mir::BasicBlock>,
+    earlier: mir::Location,
+    later: mir::Location,
+) -> bool {
+    if earlier.block == later.block {
+        earlier.statement_index < later.statement_index
+    } else {
+        dominators.is_dominated_by(later.block, earlier.block)
+    }
+}
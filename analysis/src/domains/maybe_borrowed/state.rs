@@ -0,0 +1,109 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::abstract_interpretation::AbstractState;
+use rustc_data_structures::stable_set::FxHashSet;
+use rustc_middle::mir;
+use serde::{Serialize, Serializer};
+
+/// The set of places that might be borrowed at a given program point.
+///
+/// Mutable borrows are split into two groups to model rustc's two-phase borrows: a two-phase
+/// mutable borrow is only *reserved* at the borrow site and becomes *fully active* at its first
+/// use (the machinery behind the `MUTABLE_BORROW_RESERVATION_CONFLICT` lint). While a borrow is
+/// reserved the original place is still freely readable, so such places are tracked separately
+/// in `maybe_reserved_mut_borrowed` and only move into `maybe_mut_borrowed` once the borrow is
+/// activated.
+#[derive(Clone, Default, Eq, PartialEq)]
+pub struct MaybeBorrowedState<'tcx> {
+    /// Places that might be mutably borrowed by a fully active borrow.
+    pub(super) maybe_mut_borrowed: FxHashSet<mir::Place<'tcx>>,
+    /// Places that might be borrowed by a reserved-but-not-yet-activated two-phase borrow.
+    pub(super) maybe_reserved_mut_borrowed: FxHashSet<mir::Place<'tcx>>,
+    /// Places that might be shared borrowed.
+    pub(super) maybe_shared_borrowed: FxHashSet<mir::Place<'tcx>>,
+}
+
+impl<'tcx> MaybeBorrowedState<'tcx> {
+    /// Places that might be mutably borrowed by a fully active borrow. Reserved two-phase
+    /// borrows are intentionally excluded, since they do not yet take the place away.
+    pub fn get_maybe_mut_borrowed(&self) -> &FxHashSet<mir::Place<'tcx>> {
+        &self.maybe_mut_borrowed
+    }
+
+    /// Places borrowed by a reserved-but-not-yet-activated two-phase mutable borrow.
+    pub fn get_reserved_two_phase(&self) -> &FxHashSet<mir::Place<'tcx>> {
+        &self.maybe_reserved_mut_borrowed
+    }
+
+    /// Places that might be shared borrowed.
+    pub fn get_maybe_shared_borrowed(&self) -> &FxHashSet<mir::Place<'tcx>> {
+        &self.maybe_shared_borrowed
+    }
+
+    /// Record a reserved two-phase mutable borrow of `place`.
+    pub(super) fn reserve_mut(&mut self, place: mir::Place<'tcx>) {
+        self.maybe_reserved_mut_borrowed.insert(place);
+    }
+
+    /// Activate a previously reserved two-phase mutable borrow of `place`, moving it from the
+    /// reserved set into the fully active set. This is the point at which the place stops being
+    /// accessible to the original owner.
+    pub(super) fn activate_mut(&mut self, place: mir::Place<'tcx>) {
+        self.maybe_reserved_mut_borrowed.remove(&place);
+        self.maybe_mut_borrowed.insert(place);
+    }
+
+    /// Record a non-two-phase (immediately active) mutable borrow of `place`.
+    pub(super) fn insert_mut(&mut self, place: mir::Place<'tcx>) {
+        self.maybe_mut_borrowed.insert(place);
+    }
+
+    /// Record a shared borrow of `place`.
+    pub(super) fn insert_shared(&mut self, place: mir::Place<'tcx>) {
+        self.maybe_shared_borrowed.insert(place);
+    }
+}
+
+impl<'tcx> AbstractState for MaybeBorrowedState<'tcx> {
+    fn is_bottom(&self) -> bool {
+        self.maybe_mut_borrowed.is_empty()
+            && self.maybe_reserved_mut_borrowed.is_empty()
+            && self.maybe_shared_borrowed.is_empty()
+    }
+
+    fn join(&mut self, other: &Self) {
+        self.maybe_mut_borrowed
+            .extend(other.maybe_mut_borrowed.iter().copied());
+        self.maybe_reserved_mut_borrowed
+            .extend(other.maybe_reserved_mut_borrowed.iter().copied());
+        self.maybe_shared_borrowed
+            .extend(other.maybe_shared_borrowed.iter().copied());
+    }
+
+    fn widen(&mut self, _previous: &Self) {
+        // The domain is finite, so joining is already a fixpoint accelerator.
+    }
+}
+
+impl<'tcx> Serialize for MaybeBorrowedState<'tcx> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut seq = serializer.serialize_struct("MaybeBorrowedState", 3)?;
+        let to_sorted = |set: &FxHashSet<mir::Place<'tcx>>| {
+            let mut v: Vec<_> = set.iter().map(|place| format!("{:?}", place)).collect();
+            v.sort();
+            v
+        };
+        seq.serialize_field("maybe_mut_borrowed", &to_sorted(&self.maybe_mut_borrowed))?;
+        seq.serialize_field(
+            "maybe_reserved_mut_borrowed",
+            &to_sorted(&self.maybe_reserved_mut_borrowed),
+        )?;
+        seq.serialize_field("maybe_shared_borrowed", &to_sorted(&self.maybe_shared_borrowed))?;
+        seq.end()
+    }
+}
@@ -0,0 +1,69 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AnalysisResult, FixpointEngine},
+    domains::MaybeInitializedState,
+};
+use rustc_middle::{mir, ty::TyCtxt};
+use rustc_span::def_id::DefId;
+
+/// A fixpoint analysis computing the places that are *maybe initialized* at each program point,
+/// mirroring rustc's `MaybeInitializedPlaces`. The complement of this set among the tracked
+/// places is the set of places that are *definitely uninitialized*.
+pub struct MaybeInitializedAnalysis<'mir, 'tcx: 'mir> {
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    body: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> MaybeInitializedAnalysis<'mir, 'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, def_id: DefId, body: &'mir mir::Body<'tcx>) -> Self {
+        MaybeInitializedAnalysis { tcx, def_id, body }
+    }
+}
+
+impl<'mir, 'tcx: 'mir> FixpointEngine<'mir, 'tcx> for MaybeInitializedAnalysis<'mir, 'tcx> {
+    type State = MaybeInitializedState<'mir, 'tcx>;
+
+    fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
+    fn body(&self) -> &'mir mir::Body<'tcx> {
+        self.body
+    }
+
+    fn new_bottom(&self) -> Self::State {
+        MaybeInitializedState::new_bottom(self.tcx, self.body)
+    }
+
+    fn new_initial(&self) -> Self::State {
+        MaybeInitializedState::new_initial(self.tcx, self.body)
+    }
+
+    fn need_to_widen(_counter: u32) -> bool {
+        false
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::State,
+        location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) -> AnalysisResult<()> {
+        state.apply_statement_effect(location, statement)
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &Self::State,
+        location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+    ) -> AnalysisResult<Vec<(mir::BasicBlock, Self::State)>> {
+        state.apply_terminator_effect(location, terminator)
+    }
+}
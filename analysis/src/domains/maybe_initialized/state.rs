@@ -0,0 +1,169 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{
+    abstract_interpretation::{AbstractState, AnalysisResult},
+    mir_utils::is_prefix,
+};
+use rustc_data_structures::stable_set::FxHashSet;
+use rustc_middle::{mir, ty::TyCtxt};
+
+/// The set of places that are *maybe initialized*: a place is in this set if it is initialized
+/// on at least one path reaching the program point *and* has not since been moved out of or
+/// dropped on that path. This is a forward may-analysis, mirroring rustc's
+/// `MaybeInitializedPlaces`. Its complement among the tracked places is the set of places that
+/// are *definitely uninitialized*.
+#[derive(Clone)]
+pub struct MaybeInitializedState<'mir, 'tcx: 'mir> {
+    maybe_init: FxHashSet<mir::Place<'tcx>>,
+    tcx: TyCtxt<'tcx>,
+    body: &'mir mir::Body<'tcx>,
+}
+
+impl<'mir, 'tcx: 'mir> PartialEq for MaybeInitializedState<'mir, 'tcx> {
+    fn eq(&self, other: &Self) -> bool {
+        self.maybe_init == other.maybe_init
+    }
+}
+impl<'mir, 'tcx: 'mir> Eq for MaybeInitializedState<'mir, 'tcx> {}
+
+impl<'mir, 'tcx: 'mir> MaybeInitializedState<'mir, 'tcx> {
+    /// Nothing is known to be initialized.
+    pub fn new_bottom(tcx: TyCtxt<'tcx>, body: &'mir mir::Body<'tcx>) -> Self {
+        MaybeInitializedState {
+            maybe_init: FxHashSet::default(),
+            tcx,
+            body,
+        }
+    }
+
+    /// On entry the arguments are initialized by the caller.
+    pub fn new_initial(tcx: TyCtxt<'tcx>, body: &'mir mir::Body<'tcx>) -> Self {
+        let mut maybe_init = FxHashSet::default();
+        for arg in 1..=body.arg_count {
+            maybe_init.insert(mir::Place::from(mir::Local::from_usize(arg)));
+        }
+        MaybeInitializedState {
+            maybe_init,
+            tcx,
+            body,
+        }
+    }
+
+    /// The places that are maybe initialized at this point.
+    pub fn get_maybe_init_places(&self) -> &FxHashSet<mir::Place<'tcx>> {
+        &self.maybe_init
+    }
+
+    fn gen(&mut self, place: mir::Place<'tcx>) {
+        self.maybe_init.insert(place);
+    }
+
+    fn kill(&mut self, place: mir::Place<'tcx>) {
+        // Moving out of or dropping `place` deinitializes it, all of its sub-places and all
+        // places that it is part of.
+        self.maybe_init.retain(|&p| !is_prefix(p, place) && !is_prefix(place, p));
+    }
+
+    /// A `move` operand deinitializes its source place, just like a drop.
+    fn kill_operand(&mut self, operand: &mir::Operand<'tcx>) {
+        if let mir::Operand::Move(place) = operand {
+            self.kill(*place);
+        }
+    }
+
+    /// Kill every place moved out of by the operands of `rvalue`.
+    fn kill_moved_in_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>) {
+        match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Repeat(operand, _)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand)
+            | mir::Rvalue::ShallowInitBox(operand, _) => self.kill_operand(operand),
+            mir::Rvalue::BinaryOp(_, box (left, right))
+            | mir::Rvalue::CheckedBinaryOp(_, box (left, right)) => {
+                self.kill_operand(left);
+                self.kill_operand(right);
+            }
+            mir::Rvalue::Aggregate(_, operands) => {
+                for operand in operands {
+                    self.kill_operand(operand);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn apply_statement_effect(
+        &mut self,
+        _location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) -> AnalysisResult<()> {
+        match &statement.kind {
+            mir::StatementKind::Assign(box (place, rvalue)) => {
+                // The moved-out source places are deinitialized before the target is initialized.
+                self.kill_moved_in_rvalue(rvalue);
+                self.gen(*place);
+            }
+            mir::StatementKind::StorageDead(local) => self.kill(mir::Place::from(*local)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn apply_terminator_effect(
+        &self,
+        _location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+    ) -> AnalysisResult<Vec<(mir::BasicBlock, Self)>> {
+        let mut res = Vec::new();
+        for successor in terminator.successors() {
+            let mut state = self.clone();
+            match &terminator.kind {
+                mir::TerminatorKind::Drop { place, .. } => state.kill(*place),
+                mir::TerminatorKind::DropAndReplace { place, value, .. } => {
+                    // The old value is dropped and `value` is moved into `place`.
+                    state.kill(*place);
+                    state.kill_operand(value);
+                    state.gen(*place);
+                }
+                mir::TerminatorKind::Call {
+                    func,
+                    args,
+                    destination,
+                    ..
+                } => {
+                    // Arguments (and the callee operand) passed by move are deinitialized.
+                    state.kill_operand(func);
+                    for arg in args {
+                        state.kill_operand(arg);
+                    }
+                    if let Some((place, target)) = destination {
+                        if *target == *successor {
+                            state.gen(*place);
+                        }
+                    }
+                }
+                _ => {}
+            }
+            res.push((*successor, state));
+        }
+        Ok(res)
+    }
+}
+
+impl<'mir, 'tcx: 'mir> AbstractState for MaybeInitializedState<'mir, 'tcx> {
+    fn is_bottom(&self) -> bool {
+        self.maybe_init.is_empty()
+    }
+
+    fn join(&mut self, other: &Self) {
+        // May-analysis: a place is maybe initialized if it is so on any incoming path.
+        self.maybe_init.extend(other.maybe_init.iter().copied());
+    }
+
+    fn widen(&mut self, _previous: &Self) {}
+}
@@ -0,0 +1,17 @@
+// © 2021, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+mod definitely_accessible;
+mod definitely_dropped;
+mod ever_initialized;
+mod maybe_borrowed;
+mod maybe_initialized;
+
+pub use self::definitely_accessible::*;
+pub use self::definitely_dropped::*;
+pub use self::ever_initialized::*;
+pub use self::maybe_borrowed::*;
+pub use self::maybe_initialized::*;
@@ -9,7 +9,7 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 
 impl vir::Stmt {
-    pub fn apply_on_state(&self, state: &mut State, predicates: &HashMap<String, vir::Predicate>, dropped: &mut HashSet<Perm>) {
+    pub fn apply_on_state(&self, state: &mut State, predicates: &HashMap<String, vir::Predicate>, method_contracts: &HashMap<String, (Vec<Perm>, Vec<Perm>)>, pledges: &HashMap<vir::Place, (Vec<Perm>, Vec<Perm>)>, dropped: &mut HashSet<Perm>) {
         debug!("apply_on_state '{}'", self);
         trace!("State acc {{{}}}", state.display_acc());
         trace!("State pred {{{}}}", state.display_pred());
@@ -35,21 +35,61 @@ impl vir::Stmt {
                 );
             }
 
-            &vir::Stmt::MethodCall(_, _, ref targets) => {
-                // We know that in Prusti method's preconditions and postconditions are empty
-                dropped.extend(
-                    state.pred().iter()
-                        .filter(|(p, _)| targets.contains(p.base()))
-                        .map(|(p, frac)| Perm::Pred(p.clone(), *frac))
-                );
-                dropped.extend(
-                    state.acc().iter()
-                        .filter(|(p, _)| !p.is_base() && targets.contains(p.base()))
-                        .map(|(p, frac)| Perm::Acc(p.clone(), *frac))
-                );
-                state.remove_moved_matching(|p| targets.contains(p.base()));
-                state.remove_pred_matching_place(|p| targets.contains(p.base()));
-                state.remove_acc_matching_place(|p| !p.is_base() && targets.contains(p.base()));
+            &vir::Stmt::MethodCall(ref method_name, _, ref targets) => {
+                if let Some((precondition, postcondition)) = method_contracts.get(method_name) {
+                    // The callee actually consumes and produces permissions. To keep the
+                    // fold/unfold state in sync with modular reasoning, we transfer the
+                    // permissions described by its contract: exhale the precondition footprint
+                    // and inhale the postcondition footprint (including the permissions to the
+                    // result place). In between, the permissions on `targets` are havocked.
+                    dropped.extend(
+                        precondition.iter()
+                            .filter(|p| !(p.is_acc() && p.get_place().is_base()))
+                            .cloned()
+                    );
+                    state.remove_all_perms(
+                        precondition.iter()
+                            .filter(|p| !(p.is_acc() && p.get_place().is_base()))
+                    );
+                    // Havoc any permissions still held on the call targets (the result places):
+                    // the precondition footprint only covered what is transferred in, so stale
+                    // pre-call `acc`/`pred` on the results would otherwise survive and collide
+                    // with the postcondition perms we are about to inhale.
+                    dropped.extend(
+                        state.pred().iter()
+                            .filter(|(p, _)| targets.contains(p.base()))
+                            .map(|(p, frac)| Perm::Pred(p.clone(), *frac))
+                    );
+                    dropped.extend(
+                        state.acc().iter()
+                            .filter(|(p, _)| !p.is_base() && targets.contains(p.base()))
+                            .map(|(p, frac)| Perm::Acc(p.clone(), *frac))
+                    );
+                    state.remove_moved_matching(|p| targets.contains(p.base()));
+                    state.remove_pred_matching_place(|p| targets.contains(p.base()));
+                    state.remove_acc_matching_place(|p| !p.is_base() && targets.contains(p.base()));
+                    state.insert_all_perms(
+                        postcondition.iter()
+                            .filter(|p| !(p.is_acc() && p.get_place().is_base()))
+                            .cloned()
+                    );
+                } else {
+                    // No contract is available for the callee, so fall back to havocking all
+                    // permissions that touch the call targets.
+                    dropped.extend(
+                        state.pred().iter()
+                            .filter(|(p, _)| targets.contains(p.base()))
+                            .map(|(p, frac)| Perm::Pred(p.clone(), *frac))
+                    );
+                    dropped.extend(
+                        state.acc().iter()
+                            .filter(|(p, _)| !p.is_base() && targets.contains(p.base()))
+                            .map(|(p, frac)| Perm::Acc(p.clone(), *frac))
+                    );
+                    state.remove_moved_matching(|p| targets.contains(p.base()));
+                    state.remove_pred_matching_place(|p| targets.contains(p.base()));
+                    state.remove_acc_matching_place(|p| !p.is_base() && targets.contains(p.base()));
+                }
             }
 
             &vir::Stmt::Assign(ref lhs_place, ref rhs, kind) => {
@@ -238,6 +278,29 @@ impl vir::Stmt {
                     .map(|(p, frac)| (p.clone().replace_prefix(&lhs_place, rhs_place.clone()), *frac));
                 state.insert_all_pred(new_pred_places);
 
+                // If a pledge (`after_expiry`/`assert_on_expiry`) is attached to this reborrow,
+                // its obligation becomes active exactly now. The driver keys `pledges` by the
+                // reborrow's source reference (the `rhs` of the `ExpireBorrow`), mirroring how
+                // `method_contracts` is keyed by the callee name. We exhale the footprint it
+                // reasons about in the `old` state and inhale the footprint it guarantees in the
+                // current state, keeping the pledge's two-state form in sync with the restored
+                // permissions above.
+                if let Some((old_footprint, current_footprint)) = pledges.get(rhs_place) {
+                    dropped.extend(
+                        old_footprint.iter()
+                            .filter(|p| !(p.is_acc() && p.get_place().is_base()))
+                            .cloned()
+                    );
+                    state.remove_all_perms(
+                        old_footprint.iter()
+                            .filter(|p| !(p.is_acc() && p.get_place().is_base()))
+                    );
+                    state.insert_all_perms(
+                        current_footprint.iter()
+                            .filter(|p| !(p.is_acc() && p.get_place().is_base()))
+                            .cloned()
+                    );
+                }
             }
         }
     }
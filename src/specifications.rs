@@ -36,13 +36,17 @@ pub enum SpecType {
     Postcondition,
     /// Loop invariant.
     Invariant,
+    /// Pledge that becomes active when a returned mutable reference expires,
+    /// expressed with `after_expiry` or `assert_on_expiry`.
+    Pledge,
 }
 
 #[derive(Debug)]
 /// A conversion from string into specification type error.
 pub enum TryFromStringError {
     /// Reported when the string being converted is not one of the
-    /// following: `requires`, `ensures`, `invariant`.
+    /// following: `requires`, `ensures`, `invariant`, `after_expiry`,
+    /// `assert_on_expiry`.
     UnknownSpecificationType,
 }
 
@@ -51,10 +55,16 @@ impl<'a> TryFrom<&'a str> for SpecType {
     type Error = TryFromStringError;
 
     fn try_from(typ: &str) -> Result<SpecType, TryFromStringError> {
-        match typ {
+        // Pledges may carry the referenced borrow in a generic-like suffix, as in
+        // `after_expiry<reference>(..)`; the reference is parsed out separately, so here we only
+        // look at the attribute name that precedes the `<`.
+        let name = typ.split('<').next().unwrap_or(typ).trim();
+        match name {
             "requires" => {Ok(SpecType::Precondition)},
             "ensures" => {Ok(SpecType::Postcondition)},
             "invariant" => {Ok(SpecType::Invariant)},
+            "after_expiry" => {Ok(SpecType::Pledge)},
+            "assert_on_expiry" => {Ok(SpecType::Pledge)},
             _ => {Err(TryFromStringError::UnknownSpecificationType)},
         }
     }
@@ -94,6 +104,10 @@ pub struct RawSpec {
     /// Type of this specification.
     pub spec_type: SpecType,
     /// Specification parsed as AST.
+    ///
+    /// For a `SpecType::Pledge` this is the pledge obligation, which is
+    /// interpreted in the two-state form relating the referenced place's value
+    /// when the borrow was created (`old`) to its value when it expires.
     pub expr: ptr::P<ast::Expr>,
     /// The original location of the specification.
     pub span: Span,